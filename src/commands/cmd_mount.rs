@@ -3,12 +3,261 @@ use log::{info, debug, error, LevelFilter};
 use clap::Parser;
 use uuid::Uuid;
 use std::io::{stdout, IsTerminal};
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
 use std::path::PathBuf;
 use crate::key;
 use crate::key::UnlockPolicy;
 use std::ffi::{CString, c_char, c_void};
 use std::os::unix::ffi::OsStrExt;
 
+// The fsopen/fsconfig/fsmount/move_mount family (the "new mount API", added in Linux 5.2)
+// isn't exposed by the version of `libc` we build against, so we issue the syscalls directly.
+// The numbers below are only valid on the "generic" syscall table shared by x86_64 and arm64 —
+// bcachefs-tools also ships on 32-bit arm, ppc64, s390x, mips, riscv32, etc., where these
+// numbers would hit unrelated syscalls, so the whole new-mount-API path is compiled out there
+// and those targets always use the legacy `mount(2)` fallback below.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+mod fs_mount_api {
+    use super::*;
+
+    const SYS_FSOPEN: i64 = 430;
+    const SYS_FSCONFIG: i64 = 431;
+    const SYS_FSMOUNT: i64 = 432;
+    const SYS_MOVE_MOUNT: i64 = 429;
+
+    const FSOPEN_CLOEXEC: libc::c_uint = 1;
+
+    const FSCONFIG_SET_FLAG: libc::c_uint = 0;
+    const FSCONFIG_SET_STRING: libc::c_uint = 1;
+    const FSCONFIG_SET_FD: libc::c_uint = 5;
+    const FSCONFIG_CMD_CREATE: libc::c_uint = 6;
+
+    const MOVE_MOUNT_F_EMPTY_PATH: libc::c_uint = 0x00000004;
+
+    // mount_attrs bits accepted by `fsmount`, translated from the legacy `MS_*` mountflags.
+    const MOUNT_ATTR_RDONLY: u64 = 0x00000001;
+    const MOUNT_ATTR_NOSUID: u64 = 0x00000002;
+    const MOUNT_ATTR_NODEV: u64 = 0x00000004;
+    const MOUNT_ATTR_NOEXEC: u64 = 0x00000008;
+    const MOUNT_ATTR_NOATIME: u64 = 0x00000010;
+    const MOUNT_ATTR_STRICTATIME: u64 = 0x00000020;
+    const MOUNT_ATTR_NODIRATIME: u64 = 0x00000080;
+    const MOUNT_ATTR_RELATIME: u64 = 0x00000000;
+
+    fn fsopen(fsname: &CString, flags: libc::c_uint) -> anyhow::Result<OwnedFd> {
+        let ret = unsafe { libc::syscall(SYS_FSOPEN, fsname.as_ptr(), flags) };
+        if ret < 0 {
+            return Err(crate::ErrnoError(errno::errno()).into());
+        }
+        Ok(unsafe { OwnedFd::from_raw_fd(ret as RawFd) })
+    }
+
+    fn fsconfig(
+        fd: RawFd,
+        cmd: libc::c_uint,
+        key: Option<&CString>,
+        value: Option<&CString>,
+        aux: libc::c_int,
+    ) -> anyhow::Result<()> {
+        let key_ptr = key.map_or(std::ptr::null(), |k| k.as_ptr());
+        let value_ptr = value.map_or(std::ptr::null(), |v| v.as_ptr() as *const c_void);
+
+        let ret = unsafe { libc::syscall(SYS_FSCONFIG, fd, cmd, key_ptr, value_ptr, aux) };
+        if ret < 0 {
+            // Save errno first: draining the context fd below overwrites it.
+            let err = errno::errno();
+            let msg = fsopen_error_message(fd).unwrap_or_else(|| err.to_string());
+            return Err(anyhow::anyhow!("bcachefs: {}", msg));
+        }
+        Ok(())
+    }
+
+    /// After a failing `fsconfig`, the kernel queues a human-readable explanation that can be
+    /// read back from the filesystem context fd (see `fs_context_operations.parse_param` in the
+    /// kernel). Drain it so we can report e.g. "bcachefs: unknown option foo" instead of `EINVAL`.
+    fn fsopen_error_message(fd: RawFd) -> Option<String> {
+        let mut msg = String::new();
+        let mut buf = [0u8; 256];
+        loop {
+            let n = unsafe { libc::read(fd, buf.as_mut_ptr() as *mut c_void, buf.len()) };
+            if n <= 0 {
+                break;
+            }
+            if !msg.is_empty() {
+                msg.push('\n');
+            }
+            msg.push_str(String::from_utf8_lossy(&buf[..n as usize]).trim_end());
+        }
+        if msg.is_empty() { None } else { Some(msg) }
+    }
+
+    fn fsmount(fd: RawFd, flags: libc::c_uint, mount_attrs: u64) -> anyhow::Result<OwnedFd> {
+        let ret = unsafe { libc::syscall(SYS_FSMOUNT, fd, flags, mount_attrs) };
+        if ret < 0 {
+            return Err(crate::ErrnoError(errno::errno()).into());
+        }
+        Ok(unsafe { OwnedFd::from_raw_fd(ret as RawFd) })
+    }
+
+    fn move_mount(
+        from_fd: RawFd,
+        to_path: &CString,
+        flags: libc::c_uint,
+    ) -> anyhow::Result<()> {
+        let empty = CString::new("").unwrap();
+        let ret = unsafe {
+            libc::syscall(
+                SYS_MOVE_MOUNT,
+                from_fd,
+                empty.as_ptr(),
+                libc::AT_FDCWD,
+                to_path.as_ptr(),
+                flags,
+            )
+        };
+        match ret {
+            0 => Ok(()),
+            _ => Err(crate::ErrnoError(errno::errno()).into()),
+        }
+    }
+
+    /// Translate the legacy `MS_*` mountflags bitmask into the `mount_attrs` bitmask `fsmount`
+    /// accepts. Returns `None` if `mountflags` contains a bit (e.g. `MS_SYNCHRONOUS`,
+    /// `MS_MANDLOCK`, `MS_DIRSYNC`, the `MS_LAZYTIME` extension) the new API has no equivalent
+    /// for, so the caller can fall back to legacy `mount(2)` instead of silently dropping it.
+    fn translate_mountflags(mountflags: libc::c_ulong) -> Option<u64> {
+        const TRANSLATABLE: &[(libc::c_ulong, u64)] = &[
+            (libc::MS_RDONLY, MOUNT_ATTR_RDONLY),
+            (libc::MS_NOSUID, MOUNT_ATTR_NOSUID),
+            (libc::MS_NODEV, MOUNT_ATTR_NODEV),
+            (libc::MS_NOEXEC, MOUNT_ATTR_NOEXEC),
+            (libc::MS_NOATIME, MOUNT_ATTR_NOATIME),
+            (libc::MS_STRICTATIME, MOUNT_ATTR_STRICTATIME),
+            (libc::MS_RELATIME, MOUNT_ATTR_RELATIME),
+            (libc::MS_NODIRATIME, MOUNT_ATTR_NODIRATIME),
+        ];
+
+        let mut attrs = 0u64;
+        let mut remaining = mountflags;
+        for &(flag, attr) in TRANSLATABLE {
+            if remaining & flag != 0 {
+                attrs |= attr;
+                remaining &= !flag;
+            }
+        }
+
+        if remaining != 0 { None } else { Some(attrs) }
+    }
+
+    /// Mount using the new fsopen/fsconfig/fsmount/move_mount API, which reports per-option
+    /// errors instead of collapsing everything down to a single errno. Returns `Ok(false)` (as
+    /// opposed to an `Err`) when the running kernel doesn't support the new API, or when
+    /// `mountflags` contains a bit the new API can't express, so the caller can fall back to
+    /// the legacy `mount(2)` path.
+    pub fn mount_via_fs_api(
+        devices: &str,
+        target: impl AsRef<std::path::Path>,
+        fs_opts: &[String],
+        mountflags: libc::c_ulong,
+    ) -> anyhow::Result<bool> {
+        let Some(mount_attrs) = translate_mountflags(mountflags) else {
+            return Ok(false);
+        };
+
+        let fsname = CString::new("bcachefs")?;
+        let fc = match fsopen(&fsname, FSOPEN_CLOEXEC) {
+            Ok(fc) => fc,
+            Err(_) if errno::errno().0 == libc::ENOSYS => return Ok(false),
+            Err(e) => return Err(e),
+        };
+        let fc = fc.as_raw_fd();
+
+        for opt in fs_opts {
+            let (key, value) = match opt.split_once('=') {
+                Some((k, v)) => (k, v),
+                None => (opt.as_str(), ""),
+            };
+            let key = CString::new(key)?;
+            if value.is_empty() {
+                fsconfig(fc, FSCONFIG_SET_FLAG, Some(&key), None, 0)?;
+            } else {
+                let value = CString::new(value)?;
+                fsconfig(fc, FSCONFIG_SET_STRING, Some(&key), Some(&value), 0)?;
+            }
+        }
+
+        // Prefer handing over an already-opened fd for each device (FSCONFIG_SET_FD) so the
+        // kernel doesn't have to re-resolve the path; fall back to passing the path itself
+        // (FSCONFIG_SET_STRING) if we can't open it (e.g. the caller lacks read access but the
+        // kernel, running as root, can still resolve it by path).
+        let source = CString::new("source")?;
+        for dev in devices.split(':') {
+            match std::fs::File::open(dev) {
+                Ok(file) => fsconfig(fc, FSCONFIG_SET_FD, Some(&source), None, file.as_raw_fd())?,
+                Err(_) => {
+                    let dev = CString::new(dev)?;
+                    fsconfig(fc, FSCONFIG_SET_STRING, Some(&source), Some(&dev), 0)?;
+                }
+            }
+        }
+
+        fsconfig(fc, FSCONFIG_CMD_CREATE, None, None, 0)?;
+
+        info!("mounting filesystem");
+        let mount_fd = fsmount(fc, 0, mount_attrs)?;
+        let target = CString::new(target.as_ref().as_os_str().as_bytes())?;
+        move_mount(mount_fd.as_raw_fd(), &target, MOVE_MOUNT_F_EMPTY_PATH)?;
+
+        Ok(true)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn translates_representable_flags() {
+            assert_eq!(translate_mountflags(0), Some(0));
+            assert_eq!(translate_mountflags(libc::MS_RDONLY), Some(MOUNT_ATTR_RDONLY));
+            assert_eq!(
+                translate_mountflags(libc::MS_RDONLY | libc::MS_NOATIME),
+                Some(MOUNT_ATTR_RDONLY | MOUNT_ATTR_NOATIME)
+            );
+            assert_eq!(
+                translate_mountflags(
+                    libc::MS_NOSUID | libc::MS_NODEV | libc::MS_NOEXEC | libc::MS_NODIRATIME
+                ),
+                Some(MOUNT_ATTR_NOSUID | MOUNT_ATTR_NODEV | MOUNT_ATTR_NOEXEC | MOUNT_ATTR_NODIRATIME)
+            );
+            // MS_RELATIME maps to the zero "default atime" attr bits, but should still be
+            // consumed rather than reported as unrepresentable.
+            assert_eq!(translate_mountflags(libc::MS_RELATIME), Some(0));
+        }
+
+        #[test]
+        fn rejects_unrepresentable_flags() {
+            assert_eq!(translate_mountflags(libc::MS_SYNCHRONOUS), None);
+            assert_eq!(translate_mountflags(libc::MS_MANDLOCK), None);
+            assert_eq!(translate_mountflags(libc::MS_DIRSYNC), None);
+            assert_eq!(translate_mountflags(libc::MS_REMOUNT), None);
+            // A representable flag alongside an unrepresentable one must still fall back.
+            assert_eq!(translate_mountflags(libc::MS_RDONLY | libc::MS_SYNCHRONOUS), None);
+        }
+    }
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+mod fs_mount_api {
+    pub fn mount_via_fs_api(
+        _devices: &str,
+        _target: impl AsRef<std::path::Path>,
+        _fs_opts: &[String],
+        _mountflags: libc::c_ulong,
+    ) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+}
+
 fn mount_inner(
     src: String,
     target: impl AsRef<std::path::Path>,
@@ -19,13 +268,13 @@ fn mount_inner(
 
     // bind the CStrings to keep them alive
     let src = CString::new(src)?;
-    let target = CString::new(target.as_ref().as_os_str().as_bytes())?;
+    let target_cstr = CString::new(target.as_ref().as_os_str().as_bytes())?;
     let data = data.map(CString::new).transpose()?;
     let fstype = CString::new(fstype)?;
 
     // convert to pointers for ffi
     let src = src.as_c_str().to_bytes_with_nul().as_ptr() as *const c_char;
-    let target = target.as_c_str().to_bytes_with_nul().as_ptr() as *const c_char;
+    let target_ptr = target_cstr.as_c_str().to_bytes_with_nul().as_ptr() as *const c_char;
     let data = data.as_ref().map_or(std::ptr::null(), |data| {
         data.as_c_str().to_bytes_with_nul().as_ptr() as *const c_void
     });
@@ -34,7 +283,7 @@ fn mount_inner(
     let ret = {
         info!("mounting filesystem");
         // REQUIRES: CAP_SYS_ADMIN
-        unsafe { libc::mount(src, target, fstype, mountflags, data) }
+        unsafe { libc::mount(src, target_ptr, fstype, mountflags, data) }
     };
     match ret {
         0 => Ok(()),
@@ -44,7 +293,7 @@ fn mount_inner(
 
 /// Parse a comma-separated mount options and split out mountflags and filesystem
 /// specific options.
-fn parse_mount_options(options: impl AsRef<str>) -> (Option<String>, libc::c_ulong) {
+fn parse_mount_options(options: impl AsRef<str>) -> (Vec<String>, libc::c_ulong) {
     use either::Either::*;
     debug!("parsing mount options: {}", options.as_ref());
     let (opts, flags) = options
@@ -71,19 +320,12 @@ fn parse_mount_options(options: impl AsRef<str>) -> (Option<String>, libc::c_ulo
         .fold((Vec::new(), 0), |(mut opts, flags), next| match next {
             Left(f) => (opts, flags | f),
             Right(o) => {
-                opts.push(o);
+                opts.push(o.to_string());
                 (opts, flags)
             }
         });
 
-    (
-        if opts.len() == 0 {
-            None
-        } else {
-            Some(opts.join(","))
-        },
-        flags,
-    )
+    (opts, flags)
 }
 
 fn mount(
@@ -91,12 +333,24 @@ fn mount(
     target: impl AsRef<std::path::Path>,
     options: impl AsRef<str>,
 ) -> anyhow::Result<()> {
-    let (data, mountflags) = parse_mount_options(options);
+    let (fs_opts, mountflags) = parse_mount_options(options);
 
     info!(
         "mounting bcachefs filesystem, {}",
         target.as_ref().display()
     );
+
+    match fs_mount_api::mount_via_fs_api(&device, &target, &fs_opts, mountflags) {
+        Ok(true) => return Ok(()),
+        Ok(false) => debug!("new mount API unavailable or insufficient for these options, falling back to mount(2)"),
+        Err(e) => return Err(e),
+    }
+
+    let data = if fs_opts.is_empty() {
+        None
+    } else {
+        Some(fs_opts.join(","))
+    };
     mount_inner(device, target, "bcachefs", mountflags, data)
 }
 
@@ -124,6 +378,55 @@ fn get_devices_by_uuid(uuid: Uuid) -> anyhow::Result<Vec<(PathBuf, bch_sb_handle
     Ok(devs)
 }
 
+/// Whether `devs` contains as many member devices as the superblock says the filesystem has.
+fn devices_complete(devs: &[(PathBuf, bch_sb_handle)]) -> bool {
+    match devs.first() {
+        Some((_, sb)) => devs.len() as u32 >= sb.sb().nr_devices as u32,
+        None => false,
+    }
+}
+
+/// Like `get_devices_by_uuid`, but if not all of the filesystem's member devices have shown up
+/// yet in udev, keep rescanning until they have or `wait_timeout` elapses.
+fn get_devices_by_uuid_wait(
+    uuid: Uuid,
+    wait_timeout: Option<std::time::Duration>,
+) -> anyhow::Result<Vec<(PathBuf, bch_sb_handle)>> {
+    let mut devs = get_devices_by_uuid(uuid)?;
+
+    if let Some(timeout) = wait_timeout {
+        // `--wait-for-devices` takes an arbitrary u64 number of seconds, which can be large
+        // enough that adding it to the monotonic clock overflows `Instant`. Fall back to
+        // waiting indefinitely (bounded only by repeated udev scans) rather than panicking.
+        let deadline = std::time::Instant::now().checked_add(timeout);
+        while !devices_complete(&devs)
+            && deadline.map_or(true, |d| std::time::Instant::now() < d)
+        {
+            debug!("not all member devices of {} present yet, waiting", uuid);
+            // TODO: switch to a udev monitor instead of polling once we have a clean way to
+            // bound the select() on the remaining timeout.
+            std::thread::sleep(std::time::Duration::from_millis(200));
+            devs = get_devices_by_uuid(uuid)?;
+        }
+    }
+
+    Ok(devs)
+}
+
+fn get_uuid_for_label(label: &str) -> anyhow::Result<Uuid> {
+    debug!("enumerating udev devices for label {}", label);
+    let mut udev = udev::Enumerator::new()?;
+    udev.match_subsystem("block")?;
+
+    udev.scan_devices()?
+        .into_iter()
+        .filter_map(|dev| dev.devnode().map(ToOwned::to_owned))
+        .filter_map(|dev| read_super_silent(&dev).ok())
+        .find(|sb| sb.sb().label() == label)
+        .map(|sb| sb.sb().uuid())
+        .ok_or_else(|| anyhow::anyhow!("Could not find filesystem with label {}", label))
+}
+
 fn get_uuid_for_dev_node(device: &std::path::PathBuf) ->  anyhow::Result<Uuid> {
     let mut udev = udev::Enumerator::new()?;
     udev.match_subsystem("block")?;
@@ -163,7 +466,7 @@ pub struct Cli {
     #[arg(short = 'k', long = "key_location", default_value = "ask", verbatim_doc_comment)]
     unlock_policy:     UnlockPolicy,
 
-    /// Device, or UUID=\<UUID\>
+    /// Device, UUID=\<UUID\>, or LABEL=\<label\>
     dev:            String,
 
     /// Where the filesystem should be mounted. If not set, then the filesystem
@@ -175,6 +478,17 @@ pub struct Cli {
     #[arg(short, default_value = "")]
     options:        String,
 
+    /// Wait up to this many seconds for all member devices of a multi-device filesystem to
+    /// appear before mounting. If unset, mount proceeds with whatever devices the first udev
+    /// scan finds.
+    #[arg(long)]
+    wait_for_devices: Option<u64>,
+
+    /// Allow mounting if not all member devices are present once --wait-for-devices elapses
+    /// (or immediately, if --wait-for-devices wasn't given).
+    #[arg(long)]
+    degraded:       bool,
+
     /// Force color on/off. Autodetect tty is used to define default:
     #[arg(short, long, action = clap::ArgAction::Set, default_value_t=stdout().is_terminal())]
     colorize:       bool,
@@ -184,11 +498,28 @@ pub struct Cli {
     verbose:        u8,
 }
 
-fn devs_str_sbs_from_uuid(uuid: String) -> anyhow::Result<(String, Vec<bch_sb_handle>)> {
+fn devs_str_sbs_from_uuid(
+    uuid: String,
+    wait_timeout: Option<std::time::Duration>,
+    degraded: bool,
+) -> anyhow::Result<(String, Vec<bch_sb_handle>)> {
     debug!("enumerating devices with UUID {}", uuid);
 
-    let devs_sbs = Uuid::parse_str(&uuid)
-        .map(|uuid| get_devices_by_uuid(uuid))??;
+    let uuid = Uuid::parse_str(&uuid)?;
+    let devs_sbs = get_devices_by_uuid_wait(uuid, wait_timeout)?;
+
+    // This guard only exists to back --wait-for-devices: if the caller didn't ask us to wait,
+    // keep the historical behavior of mounting with whatever udev found on the first scan
+    // (existing fstab entries/boot scripts rely on this and don't know about --degraded).
+    if wait_timeout.is_some() && !devices_complete(&devs_sbs) && !degraded {
+        return Err(anyhow::anyhow!(
+            "Timed out waiting for all member devices of {} to appear (found {}, expected {}); \
+             pass --degraded to mount anyway",
+            uuid,
+            devs_sbs.len(),
+            devs_sbs.first().map_or(0, |(_, sb)| sb.sb().nr_devices),
+        ));
+    }
 
     let devs_str = devs_sbs
         .iter()
@@ -202,19 +533,39 @@ fn devs_str_sbs_from_uuid(uuid: String) -> anyhow::Result<(String, Vec<bch_sb_ha
 
 }
 
-fn devs_str_sbs_from_device(device: &std::path::PathBuf) -> anyhow::Result<(String, Vec<bch_sb_handle>)> {
+fn devs_str_sbs_from_device(
+    device: &std::path::PathBuf,
+    wait_timeout: Option<std::time::Duration>,
+    degraded: bool,
+) -> anyhow::Result<(String, Vec<bch_sb_handle>)> {
     let bcache_fs_uuid = get_uuid_for_dev_node(device)?;
 
-    devs_str_sbs_from_uuid(bcache_fs_uuid.to_string())
+    devs_str_sbs_from_uuid(bcache_fs_uuid.to_string(), wait_timeout, degraded)
+}
+
+fn devs_str_sbs_from_label(
+    label: String,
+    wait_timeout: Option<std::time::Duration>,
+    degraded: bool,
+) -> anyhow::Result<(String, Vec<bch_sb_handle>)> {
+    let bcache_fs_uuid = get_uuid_for_label(&label)?;
+
+    devs_str_sbs_from_uuid(bcache_fs_uuid.to_string(), wait_timeout, degraded)
 }
 
 fn cmd_mount_inner(opt: Cli) -> anyhow::Result<()> {
+    let wait_timeout = opt.wait_for_devices.map(std::time::Duration::from_secs);
+    let degraded = opt.degraded;
+
     let (devices, block_devices_to_mount) = if opt.dev.starts_with("UUID=") {
         let uuid = opt.dev.replacen("UUID=", "", 1);
-        devs_str_sbs_from_uuid(uuid)?
+        devs_str_sbs_from_uuid(uuid, wait_timeout, degraded)?
     } else if opt.dev.starts_with("OLD_BLKID_UUID=") {
         let uuid = opt.dev.replacen("OLD_BLKID_UUID=", "", 1);
-        devs_str_sbs_from_uuid(uuid)?
+        devs_str_sbs_from_uuid(uuid, wait_timeout, degraded)?
+    } else if opt.dev.starts_with("LABEL=") {
+        let label = opt.dev.replacen("LABEL=", "", 1);
+        devs_str_sbs_from_label(label, wait_timeout, degraded)?
     } else {
         // If the device string contains ":" we will assume the user knows the entire list.
         // If they supply a single device it could be either the FS only has 1 device or it's
@@ -230,18 +581,27 @@ fn cmd_mount_inner(opt: Cli) -> anyhow::Result<()> {
 
             (opt.dev, block_devices_to_mount)
         } else {
-            devs_str_sbs_from_device(&PathBuf::from(opt.dev))?
+            devs_str_sbs_from_device(&PathBuf::from(opt.dev), wait_timeout, degraded)?
         }
     };
 
     if block_devices_to_mount.len() == 0 {
         Err(anyhow::anyhow!("No device found from specified parameters"))?;
     }
-    // Check if the filesystem's master key is encrypted
-    if unsafe { bcachefs::bch2_sb_is_encrypted_and_locked(block_devices_to_mount[0].sb) } {
+    // Check if the filesystem's master key is encrypted. A stale or unreadable superblock on
+    // one member shouldn't prevent unlocking via another, so try every member in turn and
+    // stop as soon as one of them unlocks (or didn't need to). A failed interactive "ask"
+    // attempt also stops the loop after that member (see below).
+    let mut unlock_errors = Vec::new();
+    for sb in &block_devices_to_mount {
+        if !unsafe { bcachefs::bch2_sb_is_encrypted_and_locked(sb.sb) } {
+            unlock_errors.clear();
+            break;
+        }
+
         // First by password_file, if available
         let fallback_to_unlock_policy = if let Some(passphrase_file) = &opt.passphrase_file {
-            match key::read_from_passphrase_file(&block_devices_to_mount[0], passphrase_file.as_path()) {
+            match key::read_from_passphrase_file(sb, passphrase_file.as_path()) {
                 Ok(()) => {
                     // Decryption succeeded
                     false
@@ -256,26 +616,73 @@ fn cmd_mount_inner(opt: Cli) -> anyhow::Result<()> {
             // No passphrase_file specified, fall back to unlock_policy
             true
         };
+
         // If decryption by key_file was unsuccesful, prompt for passphrase (or follow key_policy)
-        if fallback_to_unlock_policy {
-            key::apply_key_unlocking_policy(&block_devices_to_mount[0], opt.unlock_policy)?;
+        let result = if fallback_to_unlock_policy {
+            key::apply_key_unlocking_policy(sb, opt.unlock_policy)
+        } else {
+            Ok(())
         };
+        let was_interactive = fallback_to_unlock_policy && matches!(opt.unlock_policy, UnlockPolicy::Ask);
+
+        match result {
+            Ok(()) => {
+                unlock_errors.clear();
+                break;
+            }
+            Err(err) => {
+                unlock_errors.push(err);
+                if was_interactive {
+                    // The passphrase is a single filesystem-wide secret, not a per-member one:
+                    // if the user already typed it wrong once, retrying against the next member
+                    // would just re-prompt for what looks like the same question. Only keep
+                    // trying other members automatically for non-interactive policies, where a
+                    // member's stale/unreadable superblock (rather than user error) is the more
+                    // likely cause of failure.
+                    break;
+                }
+            }
+        }
     }
 
+    if !unlock_errors.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Failed to unlock filesystem using any member device: {}",
+            unlock_errors
+                .iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>()
+                .join("; ")
+        ));
+    }
+
+    // --degraded only suppressed the device-completeness guard above; the kernel also needs
+    // to be told to accept an incomplete device set, so pass "degraded" through as a real
+    // mount option unless the caller already specified it themselves.
+    let options = if degraded && !opt.options.split(',').any(|o| o == "degraded" || o == "very_degraded") {
+        if opt.options.is_empty() {
+            "degraded".to_string()
+        } else {
+            format!("{},degraded", opt.options)
+        }
+    } else {
+        opt.options
+    };
+
     if let Some(mountpoint) = opt.mountpoint {
         info!(
             "mounting with params: device: {}, target: {}, options: {}",
             devices,
             mountpoint.to_string_lossy(),
-            &opt.options
+            &options
         );
 
-        mount(devices, mountpoint, &opt.options)?;
+        mount(devices, mountpoint, &options)?;
     } else {
         info!(
             "would mount with params: device: {}, options: {}",
             devices,
-            &opt.options
+            &options
         );
     }
 
@@ -308,3 +715,29 @@ pub fn cmd_mount(mut argv: Vec<String>, symlink_cmd: Option<&str>) -> i32 {
         0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_mount_options_splits_flags_and_fs_opts() {
+        let (opts, flags) = parse_mount_options("ro,noatime,foo=bar,baz");
+        assert_eq!(opts, vec!["foo=bar".to_string(), "baz".to_string()]);
+        assert_eq!(flags, libc::MS_RDONLY | libc::MS_NOATIME);
+    }
+
+    #[test]
+    fn parse_mount_options_empty_string_is_no_opts_no_flags() {
+        let (opts, flags) = parse_mount_options("");
+        assert!(opts.is_empty());
+        assert_eq!(flags, 0);
+    }
+
+    #[test]
+    fn parse_mount_options_only_fs_specific() {
+        let (opts, flags) = parse_mount_options("degraded,verbose=2");
+        assert_eq!(opts, vec!["degraded".to_string(), "verbose=2".to_string()]);
+        assert_eq!(flags, 0);
+    }
+}